@@ -1,9 +1,10 @@
-use std::{thread::sleep, fs};
-use log::{info,debug};
+use std::{thread::sleep, fs, env, process};
+use log::{info,debug,error};
 use std::path::Path;
 use tokio;
 use hostname;
 
+use crate::utils::config::SourceType;
 use crate::utils::duty::Duty;
 
 mod utils;
@@ -12,21 +13,57 @@ mod modules;
 #[tokio::main]
 async fn main() {
     env_logger::init();
+
+    // `g8r validate [config.yaml]` checks the roster and duty files without applying them
+    let args: Vec<String> = env::args().collect();
+    if args.get(1).map(String::as_str) == Some("validate") {
+        let config_path = args.get(2).map(String::as_str).unwrap_or("config.yaml");
+        match utils::validate::run(config_path) {
+            Ok(true) => process::exit(0),
+            Ok(false) => process::exit(1),
+            Err(e) => {
+                eprintln!("Validation failed: {}", e);
+                process::exit(1);
+            }
+        }
+    }
     //print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
-    info!("Starting snapper ...");
+    info!("Starting snapper {} ...", env!("CARGO_PKG_VERSION"));
 
     let config = utils::config::Config::from_file("config.yaml")
                     .expect("Failed to load config");
 
     println!("Initating reconciliation loop every {}",config.refresh);
 
-    let mut iac = utils::repo::IacSync::new(&config);
-    iac.init();
+    // A local source is read in place from local_path, with no git remote
+    let mut iac = match config.source {
+        Some(SourceType::Local) => {
+            info!("Using local source {}", &config.local_path);
+            None
+        }
+        _ => {
+            let mut iac = utils::repo::IacSync::new(&config);
+            iac.init();
+            Some(iac)
+        }
+    };
 
     loop{
-        if iac.out_of_sync().unwrap() {
-            iac.reset().unwrap();
+        if let Some(iac) = iac.as_mut() {
+            if iac.out_of_sync().unwrap() {
+                iac.reset().unwrap();
+            }
         }
+
+        let pre_sync_env = match utils::pre_sync::run(&config).await {
+            Ok(exported) => exported,
+            Err(e) => {
+                error!("Pre-sync script failed, skipping reconciliation: {}", e);
+                sleep(config.refresh.into());
+                continue;
+            }
+        };
+
         // Load roster file
         let roster_path = format!("{}/{}",&config.local_path,&config.roster_path);
         info!("Loading roster file {}", &roster_path);
@@ -53,7 +90,11 @@ async fn main() {
                 let duty = Duty::new(&absolute_path)
                     .expect("Failed to create Duty from file");
 
-                let _ = duty.schedule_tasks();
+                if let Some(labels) = &duty.labels {
+                    debug!("Duty {} labels: {:?}", &duty_name, labels);
+                }
+
+                let _ = duty.schedule_tasks(&pre_sync_env);
             }
         }
 
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::io;
+
+use log::info;
+use serde::{Serialize, Deserialize};
+use crate::utils::task::TaskModule;
+
+// Test builds record every apply so ordering and scheduling can be checked
+// without touching real infrastructure
+#[cfg(test)]
+static RECORDS: std::sync::Mutex<Vec<EchoRecord>> = std::sync::Mutex::new(Vec::new());
+
+#[cfg(test)]
+#[derive(Debug, Clone)]
+pub struct EchoRecord {
+    pub action: String,
+    pub spec: serde_yaml::Value,
+    pub timestamp: std::time::SystemTime,
+}
+
+#[derive(Serialize,Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Echo {
+    message: Option<String>,
+    #[cfg(test)]
+    #[serde(skip)]
+    spec: serde_yaml::Value,
+}
+
+#[cfg(test)]
+impl Echo {
+    pub fn records() -> Vec<EchoRecord> {
+        RECORDS.lock().unwrap().clone()
+    }
+
+    pub fn clear_records() {
+        RECORDS.lock().unwrap().clear();
+    }
+}
+
+impl TaskModule for Echo {
+    fn new(config: &serde_yaml::Value) -> Result<Self, std::io::Error> {
+        let echo = serde_yaml::from_value::<Echo>(config.clone())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        #[cfg(test)]
+        let echo = Echo { spec: config.clone(), ..echo };
+        Ok(echo)
+    }
+
+    fn apply(&self, _env: &HashMap<String, String>) -> Result<(), std::io::Error> {
+        info!("{:?}",self.message.clone().unwrap_or_default());
+        #[cfg(test)]
+        RECORDS.lock().unwrap().push(EchoRecord {
+            action: "apply".to_owned(),
+            spec: self.spec.clone(),
+            timestamp: std::time::SystemTime::now(),
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::task::Task;
+
+    #[test]
+    fn apply_is_recorded() {
+        Echo::clear_records();
+        let config: serde_yaml::Value = serde_yaml::from_str("echo:\n  message: hello\n").unwrap();
+
+        Task::new(&config).unwrap().apply(&HashMap::new()).unwrap();
+
+        let records = Echo::records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].action, "apply");
+        assert_eq!(records[0].spec["message"], "hello");
+        assert!(records[0].timestamp <= std::time::SystemTime::now());
+
+        Echo::clear_records();
+        assert!(Echo::records().is_empty());
+    }
+}
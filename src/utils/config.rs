@@ -1,5 +1,6 @@
 use core::fmt;
 use std::fs;
+use std::io;
 use std::path::Path;
 use std::error::Error;
 use serde::{Serialize, Deserialize};
@@ -9,15 +10,41 @@ use duration_string::DurationString;
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Config {
     // Define your configuration fields here
+    pub source: Option<SourceType>,
+    // Git settings may be omitted when source is local
+    #[serde(default)]
     pub repo: String,
+    #[serde(default)]
     pub branch: String,
     pub refresh: DurationString,
+    #[serde(default)]
     pub token: String,
+    #[serde(default)]
     pub tag: String,
+    #[serde(default)]
     pub username: String,
+    pub git_provider: Option<GitProvider>,
     pub local_path: String,
     pub roster_path: String,
     pub duties_path: String,
+    pub pre_sync_script: Option<String>,
+    pub pre_sync_timeout: Option<DurationString>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceType {
+    Git,
+    Local,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum GitProvider {
+    Github,
+    Gitlab,
+    Bitbucket,
+    Generic,
 }
 
 impl fmt::Display for Config {
@@ -29,7 +56,41 @@ impl fmt::Display for Config {
 impl Config {
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
         let contents = fs::read_to_string(path)?;
-        let config = serde_yaml::from_str(&contents)?;
+        let config: Config = serde_yaml::from_str(&contents)?;
+        config.validate()?;
         Ok(config)
     }
+
+    // The git settings default to empty so local sources can omit them,
+    // but a git source cannot work without a repository URL
+    fn validate(&self) -> Result<(), Box<dyn Error>> {
+        if self.source != Some(SourceType::Local) && self.repo.is_empty() {
+            return Err(Box::new(io::Error::new(io::ErrorKind::InvalidData,
+                "missing field `repo` (required unless source is local)")));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "refresh: 10s\nlocal_path: repo\nroster_path: roster.yaml\nduties_path: duties/\n";
+
+    fn parse(extra: &str) -> Config {
+        serde_yaml::from_str(&format!("{}{}", BASE, extra)).unwrap()
+    }
+
+    #[test]
+    fn git_source_requires_repo() {
+        assert!(parse("").validate().is_err());
+        assert!(parse("source: git\n").validate().is_err());
+        assert!(parse("repo: https://github.com/o/r.git\n").validate().is_ok());
+    }
+
+    #[test]
+    fn local_source_needs_no_repo() {
+        assert!(parse("source: local\n").validate().is_ok());
+    }
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
 use core::fmt;
 use std::error::Error;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 
 use super::task::{Task};
@@ -8,6 +9,8 @@ use super::task::{Task};
 #[derive(Serialize,Deserialize)]
 pub struct Duty{
     pub base: String,
+    // Free-form labels for grouping duties, e.g. environment: prod
+    pub labels: Option<HashMap<String, String>>,
     pub tasks: Vec<serde_yaml::Value>,
 }
 
@@ -18,11 +21,11 @@ impl Duty {
         Ok(duty)
     }
 
-    pub fn schedule_tasks(&self) -> Result<(), std::io::Error> {
+    pub fn schedule_tasks(&self, env: &HashMap<String, String>) -> Result<(), std::io::Error> {
         for task in &self.tasks {
             let mut t = Task::new(task)
                 .expect("Problem defining task");
-            t.apply().expect("Problem applying module logic");
+            t.apply(env).expect("Problem applying module logic");
         }
         Ok(())
     } 
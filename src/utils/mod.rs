@@ -2,4 +2,6 @@ pub mod config;
 pub mod repo;
 pub mod roster;
 pub mod duty;
-pub mod task;
\ No newline at end of file
+pub mod task;
+pub mod pre_sync;
+pub mod validate;
\ No newline at end of file
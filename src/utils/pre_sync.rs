@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::io;
+use std::time::Duration;
+use log::{debug, info};
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::utils;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+// Runs the configured pre_sync_script before duties are applied.
+// Lines printed as KEY=VALUE are returned and handed to every task's apply;
+// the process environment is left untouched.
+pub async fn run(config: &utils::config::Config) -> Result<HashMap<String, String>, Box<dyn Error>> {
+    let script = match &config.pre_sync_script {
+        Some(script) => script,
+        None => return Ok(HashMap::new()),
+    };
+
+    if has_parent_segment(script) {
+        return Err(Box::new(io::Error::new(io::ErrorKind::InvalidInput,
+            "pre_sync_script must not contain '..' path segments")));
+    }
+
+    let limit = config.pre_sync_timeout
+        .map(Duration::from)
+        .unwrap_or(DEFAULT_TIMEOUT);
+
+    info!("Running pre-sync script {}", script);
+    let output = timeout(limit, Command::new("sh")
+            .arg("-c")
+            .arg(script)
+            .current_dir(&config.local_path)
+            .kill_on_drop(true)
+            .output())
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::TimedOut,
+            format!("pre_sync_script timed out after {:?}", limit)))??;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    debug!("pre-sync stdout: {}", stdout);
+    debug!("pre-sync stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    if !output.status.success() {
+        return Err(Box::new(io::Error::other(
+            format!("pre_sync_script exited with {}", output.status))));
+    }
+
+    Ok(parse_exports(&stdout))
+}
+
+// Splits on path separators and shell metacharacters so forms like
+// `cd ..;`, `FOO=../x` or `"../secret"` are caught, but `git log a..b` is not
+const SEGMENT_SEPARATORS: &[char] = &['/', ';', '&', '|', '(', ')', '{', '}', '<', '>', '=', '"', '\'', '`', '$'];
+
+fn has_parent_segment(script: &str) -> bool {
+    script.split(|c: char| c.is_whitespace() || SEGMENT_SEPARATORS.contains(&c))
+        .any(|part| part == "..")
+}
+
+fn parse_exports(stdout: &str) -> HashMap<String, String> {
+    stdout.lines()
+        .filter_map(|line| line.trim().split_once('='))
+        .filter(|(key, _)| is_valid_key(key))
+        .map(|(key, value)| (key.to_owned(), value.to_owned()))
+        .collect()
+}
+
+// Same rule as POSIX shell variable names
+fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {},
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs};
+    use crate::utils::config::Config;
+
+    fn config_with(script: &str, local_path: &str) -> Config {
+        let mut config: Config = serde_yaml::from_str(
+            "source: local\nrefresh: 10s\nroster_path: roster.yaml\nduties_path: duties/\nlocal_path: .\n").unwrap();
+        config.local_path = local_path.to_owned();
+        config.pre_sync_script = Some(script.to_owned());
+        config
+    }
+
+    fn io_kind(err: Box<dyn Error>) -> io::ErrorKind {
+        err.downcast::<io::Error>().unwrap().kind()
+    }
+
+    #[tokio::test]
+    async fn exports_are_returned() {
+        let exported = run(&config_with("echo A=1", ".")).await.unwrap();
+
+        assert_eq!(exported, HashMap::from([("A".to_owned(), "1".to_owned())]));
+    }
+
+    #[tokio::test]
+    async fn non_zero_exit_fails() {
+        assert!(run(&config_with("exit 3", ".")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn slow_script_times_out() {
+        let mut config = config_with("sleep 5", ".");
+        config.pre_sync_timeout = Some("100ms".parse().unwrap());
+
+        let err = run(&config).await.unwrap_err();
+        assert_eq!(io_kind(err), io::ErrorKind::TimedOut);
+    }
+
+    #[tokio::test]
+    async fn script_runs_in_local_path() {
+        let dir = env::temp_dir().join(format!("g8r-pre-sync-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let exported = run(&config_with("echo DIR=$(pwd -P)", dir.to_str().unwrap())).await.unwrap();
+        assert_eq!(exported["DIR"], fs::canonicalize(&dir).unwrap().to_str().unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn parse_exports_keeps_valid_keys_only() {
+        let exported = parse_exports("AWS_PROFILE=prod\n_X=1\n1X=y\nnot an export\n=empty\nBAD-KEY=z\nURL=a=b\n");
+
+        assert_eq!(exported.len(), 3);
+        assert_eq!(exported["AWS_PROFILE"], "prod");
+        assert_eq!(exported["_X"], "1");
+        assert_eq!(exported["URL"], "a=b");
+    }
+
+    #[test]
+    fn parent_segments_are_rejected() {
+        assert!(has_parent_segment("../refresh.sh"));
+        assert!(has_parent_segment("sh scripts/../../refresh.sh"));
+        assert!(has_parent_segment("cd ..; ./x.sh"));
+        assert!(has_parent_segment("cd ..&&./x.sh"));
+        assert!(has_parent_segment("FOO=../x ./a.sh"));
+        assert!(has_parent_segment("cat \"../secret\""));
+        assert!(has_parent_segment("cat '../secret'"));
+        assert!(has_parent_segment("(cd ..)"));
+        assert!(has_parent_segment("cat $(echo ..)"));
+        assert!(has_parent_segment("ls ..|wc -l"));
+        assert!(has_parent_segment("cat <../secret"));
+        assert!(!has_parent_segment("echo ..."));
+        assert!(!has_parent_segment("git log a..b"));
+        assert!(!has_parent_segment("./refresh.sh"));
+    }
+}
@@ -4,6 +4,7 @@ use std::{path::Path};
 use url::{Url};
 
 use crate::utils;
+use crate::utils::config::GitProvider;
 
 pub struct IacSync {
     local: Option<git2::Repository>,
@@ -32,7 +33,8 @@ impl IacSync {
                 .expect("Unable to parse URL");
 
             // Interpolate values to authenticate via oauth token
-            configured_url.set_username(&self.config.username)
+            let username = credential_username(self.config.git_provider, &self.config.username, &configured_url);
+            configured_url.set_username(&username)
                 .expect("Unable to set username");
             configured_url.set_password(Some(&self.config.token))
                 .expect("Unable to set password");
@@ -103,4 +105,83 @@ impl IacSync {
         )
     }
     
-}
\ No newline at end of file
+}
+
+fn detect_provider(url: &Url) -> GitProvider {
+    match url.host_str() {
+        Some(host) if host.contains("github") => GitProvider::Github,
+        Some(host) if host.contains("gitlab") => GitProvider::Gitlab,
+        Some(host) if host.contains("bitbucket") => GitProvider::Bitbucket,
+        _ => GitProvider::Generic,
+    }
+}
+
+// An explicit git_provider decides the username. When the provider is only
+// detected from the URL, a configured username is kept as-is so existing
+// setups such as GitLab deploy tokens keep working.
+fn credential_username(provider: Option<GitProvider>, username: &str, url: &Url) -> String {
+    match provider {
+        Some(provider) => {
+            let chosen = provider_username(provider, username);
+            if !username.is_empty() && chosen != username {
+                info!("Using username {} for {:?} instead of configured {}", &chosen, provider, username);
+            }
+            chosen
+        }
+        None if username.is_empty() => provider_username(detect_provider(url), username),
+        None => username.to_owned(),
+    }
+}
+
+// GitHub and GitLab accept tokens under "oauth2", Bitbucket app passwords
+// need the real account name
+fn provider_username(provider: GitProvider, username: &str) -> String {
+    match provider {
+        GitProvider::Github | GitProvider::Gitlab => "oauth2".to_owned(),
+        GitProvider::Bitbucket => username.to_owned(),
+        GitProvider::Generic if username.is_empty() => "git".to_owned(),
+        GitProvider::Generic => username.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    #[test]
+    fn detects_provider_from_host() {
+        assert_eq!(detect_provider(&url("https://github.com/o/r.git")), GitProvider::Github);
+        assert_eq!(detect_provider(&url("https://gitlab.example.com/o/r.git")), GitProvider::Gitlab);
+        assert_eq!(detect_provider(&url("https://bitbucket.org/o/r.git")), GitProvider::Bitbucket);
+        assert_eq!(detect_provider(&url("https://git.example.com/o/r.git")), GitProvider::Generic);
+    }
+
+    #[test]
+    fn explicit_provider_decides_username() {
+        let repo = url("https://git.example.com/o/r.git");
+        assert_eq!(credential_username(Some(GitProvider::Github), "alice", &repo), "oauth2");
+        assert_eq!(credential_username(Some(GitProvider::Gitlab), "alice", &repo), "oauth2");
+        assert_eq!(credential_username(Some(GitProvider::Bitbucket), "alice", &repo), "alice");
+        assert_eq!(credential_username(Some(GitProvider::Generic), "alice", &repo), "alice");
+        assert_eq!(credential_username(Some(GitProvider::Generic), "", &repo), "git");
+    }
+
+    #[test]
+    fn detected_provider_keeps_configured_username() {
+        assert_eq!(credential_username(None, "gitlab+deploy-token-1", &url("https://gitlab.com/o/r.git")), "gitlab+deploy-token-1");
+        assert_eq!(credential_username(None, "alice", &url("https://github.com/o/r.git")), "alice");
+        assert_eq!(credential_username(None, "alice", &url("https://bitbucket.org/o/r.git")), "alice");
+        assert_eq!(credential_username(None, "alice", &url("https://git.example.com/o/r.git")), "alice");
+    }
+
+    #[test]
+    fn detected_provider_fills_empty_username() {
+        assert_eq!(credential_username(None, "", &url("https://github.com/o/r.git")), "oauth2");
+        assert_eq!(credential_username(None, "", &url("https://gitlab.com/o/r.git")), "oauth2");
+        assert_eq!(credential_username(None, "", &url("https://git.example.com/o/r.git")), "git");
+    }
+}
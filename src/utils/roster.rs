@@ -11,7 +11,34 @@ pub struct Roster{
 impl Roster {
     pub fn new(file_path: &str) -> Result<Self, Box<dyn Error>> {
         let file_content = fs::read_to_string(file_path)?;
-        let roster: Roster = serde_yaml::from_str(&file_content)?;
+        Self::from_yaml(&file_content)
+    }
+
+    pub fn from_yaml(contents: &str) -> Result<Self, Box<dyn Error>> {
+        // Go through Value first: its Mapping rejects duplicate keys, whereas a
+        // HashMap would let a second duty of the same name overwrite the first
+        let value: serde_yaml::Value = serde_yaml::from_str(contents)?;
+        let roster: Roster = serde_yaml::from_value(value)?;
         Ok(roster)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicate_duties_are_rejected() {
+        let err = Roster::from_yaml("duties:\n  web: [a]\n  web: [b]\n").unwrap_err();
+
+        assert!(err.to_string().contains("duplicate entry with key \"web\""), "{}", err);
+    }
+
+    #[test]
+    fn duties_are_loaded() {
+        let roster = Roster::from_yaml("duties:\n  web: [a, b]\n  db: [c]\n").unwrap();
+
+        assert_eq!(roster.duties["web"], vec!["a", "b"]);
+        assert_eq!(roster.duties["db"], vec!["c"]);
+    }
 }
\ No newline at end of file
@@ -1,10 +1,14 @@
+use std::collections::HashMap;
+
 use crate::modules::{echo::Echo, systemd_unit::SystemdUnit};
 
 pub trait TaskModule {
     fn new(config: &serde_yaml::Value) -> Result<Self, std::io::Error>
     where
         Self: Sized; 
-    fn apply(&self) -> Result<(), std::io::Error>;
+    // env holds the variables exported by the pre-sync script; modules that
+    // start processes should pass it on with Command::envs
+    fn apply(&self, env: &HashMap<String, String>) -> Result<(), std::io::Error>;
 }
 
 pub struct ModuleFactory;
@@ -36,7 +40,7 @@ impl Task {
         Ok(Task { module })
     }
 
-    pub fn apply(&self) -> Result<(), std::io::Error> {
-        self.module.apply()
+    pub fn apply(&self, env: &HashMap<String, String>) -> Result<(), std::io::Error> {
+        self.module.apply(env)
     }
 }
\ No newline at end of file
@@ -0,0 +1,93 @@
+use std::error::Error;
+use log::info;
+
+use crate::utils;
+use crate::utils::duty::Duty;
+use crate::utils::task::Task;
+
+// Loads the roster and every duty it lists, building each task's module
+// without applying it. Returns false if any duty failed to validate.
+pub fn run(config_path: &str) -> Result<bool, Box<dyn Error>> {
+    let config = utils::config::Config::from_file(config_path)?;
+
+    let roster_path = format!("{}/{}", &config.local_path, &config.roster_path);
+    info!("Validating roster file {}", &roster_path);
+    let roster = utils::roster::Roster::new(&roster_path)?;
+
+    let mut duty_names: Vec<&String> = roster.duties.keys().collect();
+    duty_names.sort();
+
+    let mut valid = true;
+    for duty_name in duty_names {
+        let duty_file_path = format!("{}/{}{}.yaml", &config.local_path, &config.duties_path, duty_name);
+        match validate_duty(&duty_file_path) {
+            Ok(task_count) => println!("ok      {} ({} tasks)", duty_name, task_count),
+            Err(e) => {
+                println!("FAILED  {}: {}", duty_name, e);
+                valid = false;
+            }
+        }
+    }
+    Ok(valid)
+}
+
+fn validate_duty(file_path: &str) -> Result<usize, Box<dyn Error>> {
+    let duty = Duty::new(file_path)?;
+    for task in &duty.tasks {
+        Task::new(task)?;
+    }
+    Ok(duty.tasks.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::{env, fs, process};
+
+    // Lays out config.yaml, roster.yaml and duties/<name>.yaml in a fresh temp dir
+    fn workspace(name: &str, duties: &[(&str, &str)]) -> PathBuf {
+        let dir = env::temp_dir().join(format!("g8r-validate-{}-{}", name, process::id()));
+        fs::create_dir_all(dir.join("duties")).unwrap();
+
+        fs::write(dir.join("config.yaml"), format!(
+            "source: local\nrefresh: 10s\nlocal_path: {}\nroster_path: roster.yaml\nduties_path: duties/\n",
+            dir.display())).unwrap();
+
+        let mut roster = String::from("duties:\n");
+        for (duty_name, content) in duties {
+            roster.push_str(&format!("  {}: [host]\n", duty_name));
+            fs::write(dir.join("duties").join(format!("{}.yaml", duty_name)), content).unwrap();
+        }
+        fs::write(dir.join("roster.yaml"), roster).unwrap();
+        dir
+    }
+
+    const VALID: &str = "base: b\ntasks:\n  - echo:\n      message: hi\n";
+    const UNKNOWN_MODULE: &str = "base: b\ntasks:\n  - nope: {}\n";
+    const MISSPELLED_FIELD: &str = "base: b\ntasks:\n  - echo:\n      mesage: hi\n";
+
+    #[test]
+    fn validate_duty_checks_each_task() {
+        let dir = workspace("duty", &[("web", VALID), ("bad", UNKNOWN_MODULE), ("typo", MISSPELLED_FIELD)]);
+        let duty_path = |name: &str| dir.join("duties").join(format!("{}.yaml", name)).display().to_string();
+
+        assert_eq!(validate_duty(&duty_path("web")).unwrap(), 1);
+        assert!(validate_duty(&duty_path("bad")).unwrap_err().to_string().contains("Unknown module type"));
+        assert!(validate_duty(&duty_path("typo")).unwrap_err().to_string().contains("unknown field `mesage`"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn run_reports_overall_result() {
+        let good = workspace("good", &[("web", VALID)]);
+        let bad = workspace("bad", &[("web", VALID), ("bad", UNKNOWN_MODULE)]);
+
+        assert!(run(good.join("config.yaml").to_str().unwrap()).unwrap());
+        assert!(!run(bad.join("config.yaml").to_str().unwrap()).unwrap());
+
+        fs::remove_dir_all(&good).unwrap();
+        fs::remove_dir_all(&bad).unwrap();
+    }
+}